@@ -0,0 +1,1075 @@
+use std::collections::BTreeSet;
+
+use cosmwasm_std::{
+    to_binary, Addr, Api, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdError,
+    StdResult, Storage, Uint128, WasmMsg,
+};
+use cw20::{
+    AllAccountsResponse, AllAllowancesResponse, AllSpenderAllowancesResponse, AllowanceInfo,
+    AllowanceResponse, BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20ReceiveMsg,
+    DownloadLogoResponse, Logo, LogoInfo, MarketingInfoResponse, MinterResponse,
+    SpenderAllowanceInfo, TokenInfoResponse,
+};
+use cw_storage_plus::Bound;
+use cw_utils::Expiration;
+
+use crate::error::ContractError;
+use crate::msg::{
+    AllowlistExecuteMsg, ExecuteMsg, InstantiateMarketingInfo, InstantiateMsg,
+    IsTransferAllowedResponse, QueryMsg,
+};
+use crate::state::{
+    MinterData, TokenInfo, ALLOWANCES, ALLOWLIST, BALANCES, LOGO, MARKETING_INFO, TOKEN_INFO,
+    TRANSFER_RESTRICTION_ADMIN,
+};
+
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+// embedded logos are capped the same way the upstream cw20-base caps them
+const LOGO_SIZE_CAP: usize = 5 * 1024;
+
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    msg.validate()?;
+
+    let total_supply = create_accounts(deps.storage, &msg.initial_balances)?;
+
+    let cap = msg.get_cap();
+    if let Some(limit) = cap {
+        if total_supply > limit {
+            return Err(ContractError::CannotExceedCap {});
+        }
+    }
+
+    let mint = match msg.mint {
+        Some(m) => Some(MinterData {
+            minter: deps.api.addr_validate(&m.minter)?,
+            cap: m.cap,
+        }),
+        None => None,
+    };
+
+    let token_info = TokenInfo {
+        name: msg.name,
+        symbol: msg.symbol,
+        decimals: msg.decimals,
+        total_supply,
+        mint,
+    };
+    TOKEN_INFO.save(deps.storage, &token_info)?;
+
+    if let Some(marketing) = msg.marketing {
+        save_marketing_info(deps.storage, deps.api, marketing)?;
+    }
+
+    if let Some(restriction) = msg.transfer_restriction {
+        let admin = deps.api.addr_validate(&restriction.admin)?;
+        TRANSFER_RESTRICTION_ADMIN.set(deps.storage, Some(admin))?;
+    }
+
+    Ok(Response::new())
+}
+
+fn create_accounts(
+    storage: &mut dyn Storage,
+    accounts: &[Cw20Coin],
+) -> Result<Uint128, ContractError> {
+    let mut total_supply = Uint128::zero();
+    let mut seen = BTreeSet::new();
+    for row in accounts {
+        if !seen.insert(row.address.clone()) {
+            return Err(ContractError::DuplicateInitialBalanceAddress {});
+        }
+        let address = Addr::unchecked(&row.address);
+        BALANCES.save(storage, &address, &row.amount)?;
+        total_supply += row.amount;
+    }
+    Ok(total_supply)
+}
+
+fn save_marketing_info(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    marketing: InstantiateMarketingInfo,
+) -> Result<(), ContractError> {
+    let marketing_addr = marketing
+        .marketing
+        .map(|a| api.addr_validate(&a))
+        .transpose()?
+        .map(|a| a.to_string());
+    MARKETING_INFO.save(
+        storage,
+        &MarketingInfoResponse {
+            project: marketing.project,
+            description: marketing.description,
+            marketing: marketing_addr,
+            logo: None,
+        },
+    )?;
+    Ok(())
+}
+
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Base(base) => execute_base(deps, env, info, base),
+        ExecuteMsg::Allowlist(allowlist) => execute_allowlist(deps, info, allowlist),
+    }
+}
+
+fn execute_base(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: Cw20ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        Cw20ExecuteMsg::Transfer { recipient, amount } => {
+            execute_transfer(deps, info, recipient, amount)
+        }
+        Cw20ExecuteMsg::Burn { amount } => execute_burn(deps, info, amount),
+        Cw20ExecuteMsg::Send {
+            contract,
+            amount,
+            msg,
+        } => execute_send(deps, info, contract, amount, msg),
+        Cw20ExecuteMsg::IncreaseAllowance {
+            spender,
+            amount,
+            expires,
+        } => execute_increase_allowance(deps, env, info, spender, amount, expires),
+        Cw20ExecuteMsg::DecreaseAllowance {
+            spender,
+            amount,
+            expires,
+        } => execute_decrease_allowance(deps, env, info, spender, amount, expires),
+        Cw20ExecuteMsg::TransferFrom {
+            owner,
+            recipient,
+            amount,
+        } => execute_transfer_from(deps, env, info, owner, recipient, amount),
+        Cw20ExecuteMsg::SendFrom {
+            owner,
+            contract,
+            amount,
+            msg,
+        } => execute_send_from(deps, env, info, owner, contract, amount, msg),
+        Cw20ExecuteMsg::BurnFrom { owner, amount } => {
+            execute_burn_from(deps, env, info, owner, amount)
+        }
+        Cw20ExecuteMsg::Mint { recipient, amount } => execute_mint(deps, info, recipient, amount),
+        Cw20ExecuteMsg::UpdateMinter { new_minter } => {
+            execute_update_minter(deps, info, new_minter)
+        }
+        Cw20ExecuteMsg::UpdateMarketing {
+            project,
+            description,
+            marketing,
+        } => execute_update_marketing(deps, info, project, description, marketing),
+        Cw20ExecuteMsg::UploadLogo(logo) => execute_upload_logo(deps, info, logo),
+    }
+}
+
+fn execute_allowlist(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: AllowlistExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        AllowlistExecuteMsg::AddToAllowlist { addresses } => {
+            execute_add_to_allowlist(deps, info, addresses)
+        }
+        AllowlistExecuteMsg::RemoveFromAllowlist { addresses } => {
+            execute_remove_from_allowlist(deps, info, addresses)
+        }
+    }
+}
+
+/// With `transfer_restriction` unset, always Ok. Otherwise errs unless both `sender` and
+/// `counterparty` are on `ALLOWLIST`. Mint/burn don't have a counterparty and aren't gated by this.
+fn assert_transfer_allowed(
+    storage: &dyn Storage,
+    sender: &Addr,
+    counterparty: &Addr,
+) -> Result<(), ContractError> {
+    if TRANSFER_RESTRICTION_ADMIN.get(storage)?.is_none() {
+        return Ok(());
+    }
+    if ALLOWLIST.may_load(storage, sender)?.is_none() {
+        return Err(ContractError::NotAllowlisted {
+            address: sender.to_string(),
+        });
+    }
+    if ALLOWLIST.may_load(storage, counterparty)?.is_none() {
+        return Err(ContractError::NotAllowlisted {
+            address: counterparty.to_string(),
+        });
+    }
+    Ok(())
+}
+
+fn execute_transfer(
+    deps: DepsMut,
+    info: MessageInfo,
+    recipient: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+    let recipient_addr = deps.api.addr_validate(&recipient)?;
+    assert_transfer_allowed(deps.storage, &info.sender, &recipient_addr)?;
+
+    BALANCES.update(
+        deps.storage,
+        &info.sender,
+        |bal| -> Result<_, ContractError> { Ok(bal.unwrap_or_default().checked_sub(amount)?) },
+    )?;
+    BALANCES.update(
+        deps.storage,
+        &recipient_addr,
+        |bal| -> Result<_, ContractError> { Ok(bal.unwrap_or_default().checked_add(amount)?) },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "transfer")
+        .add_attribute("from", info.sender)
+        .add_attribute("to", recipient)
+        .add_attribute("amount", amount))
+}
+
+fn execute_burn(
+    deps: DepsMut,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+    BALANCES.update(
+        deps.storage,
+        &info.sender,
+        |bal| -> Result<_, ContractError> { Ok(bal.unwrap_or_default().checked_sub(amount)?) },
+    )?;
+    TOKEN_INFO.update(deps.storage, |mut token_info| -> Result<_, ContractError> {
+        token_info.total_supply = token_info.total_supply.checked_sub(amount)?;
+        Ok(token_info)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "burn")
+        .add_attribute("from", info.sender)
+        .add_attribute("amount", amount))
+}
+
+fn execute_mint(
+    deps: DepsMut,
+    info: MessageInfo,
+    recipient: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+    let mut config = TOKEN_INFO.load(deps.storage)?;
+    if config.mint.as_ref().map(|m| &m.minter) != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    config.total_supply += amount;
+    if let Some(limit) = config.mint.as_ref().and_then(|m| m.cap) {
+        if config.total_supply > limit {
+            return Err(ContractError::CannotExceedCap {});
+        }
+    }
+    TOKEN_INFO.save(deps.storage, &config)?;
+
+    let recipient_addr = deps.api.addr_validate(&recipient)?;
+    BALANCES.update(
+        deps.storage,
+        &recipient_addr,
+        |bal| -> Result<_, ContractError> { Ok(bal.unwrap_or_default().checked_add(amount)?) },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "mint")
+        .add_attribute("to", recipient)
+        .add_attribute("amount", amount))
+}
+
+fn execute_update_minter(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_minter: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config = TOKEN_INFO.load(deps.storage)?;
+    if config.mint.as_ref().map(|m| &m.minter) != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let minter_data = new_minter
+        .map(|new_minter| -> Result<_, ContractError> {
+            Ok(MinterData {
+                minter: deps.api.addr_validate(&new_minter)?,
+                cap: config.mint.as_ref().and_then(|m| m.cap),
+            })
+        })
+        .transpose()?;
+    config.mint = minter_data;
+    TOKEN_INFO.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_minter"))
+}
+
+fn execute_send(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract: String,
+    amount: Uint128,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+    let contract_addr = deps.api.addr_validate(&contract)?;
+    assert_transfer_allowed(deps.storage, &info.sender, &contract_addr)?;
+
+    BALANCES.update(
+        deps.storage,
+        &info.sender,
+        |bal| -> Result<_, ContractError> { Ok(bal.unwrap_or_default().checked_sub(amount)?) },
+    )?;
+    BALANCES.update(
+        deps.storage,
+        &contract_addr,
+        |bal| -> Result<_, ContractError> { Ok(bal.unwrap_or_default().checked_add(amount)?) },
+    )?;
+
+    let send = Cw20ReceiveMsg {
+        sender: info.sender.to_string(),
+        amount,
+        msg,
+    };
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: contract.clone(),
+            msg: to_binary(&send)?,
+            funds: vec![],
+        })
+        .add_attribute("action", "send")
+        .add_attribute("from", info.sender)
+        .add_attribute("to", contract)
+        .add_attribute("amount", amount))
+}
+
+fn execute_increase_allowance(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    spender: String,
+    amount: Uint128,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let spender_addr = deps.api.addr_validate(&spender)?;
+    if spender_addr == info.sender {
+        return Err(ContractError::CannotSetOwnAccount {});
+    }
+    if let Some(exp) = &expires {
+        if exp.is_expired(&env.block) {
+            return Err(ContractError::Expired {});
+        }
+    }
+
+    ALLOWANCES.update(
+        deps.storage,
+        (&info.sender, &spender_addr),
+        |allow| -> Result<_, ContractError> {
+            let mut allow = allow.unwrap_or(AllowanceResponse {
+                allowance: Uint128::zero(),
+                expires: Expiration::Never {},
+            });
+            allow.allowance += amount;
+            if let Some(exp) = expires {
+                allow.expires = exp;
+            }
+            Ok(allow)
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "increase_allowance")
+        .add_attribute("owner", info.sender)
+        .add_attribute("spender", spender)
+        .add_attribute("amount", amount))
+}
+
+fn execute_decrease_allowance(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    spender: String,
+    amount: Uint128,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let spender_addr = deps.api.addr_validate(&spender)?;
+    if spender_addr == info.sender {
+        return Err(ContractError::CannotSetOwnAccount {});
+    }
+    if let Some(exp) = &expires {
+        if exp.is_expired(&env.block) {
+            return Err(ContractError::Expired {});
+        }
+    }
+
+    let key = (&info.sender, &spender_addr);
+    let mut allow = ALLOWANCES
+        .may_load(deps.storage, key)?
+        .ok_or(ContractError::NoAllowance {})?;
+    allow.allowance = allow
+        .allowance
+        .checked_sub(amount)
+        .map_err(|_| ContractError::NoAllowance {})?;
+    if let Some(exp) = expires {
+        allow.expires = exp;
+    }
+    if allow.allowance.is_zero() {
+        ALLOWANCES.remove(deps.storage, key);
+    } else {
+        ALLOWANCES.save(deps.storage, key, &allow)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "decrease_allowance")
+        .add_attribute("owner", info.sender)
+        .add_attribute("spender", spender)
+        .add_attribute("amount", amount))
+}
+
+/// Deducts `amount` from the `owner -> spender` allowance, erroring if unset, expired, or
+/// insufficient. Called before every `TransferFrom`/`SendFrom`/`BurnFrom`.
+fn deduct_allowance(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    spender: &Addr,
+    env: &Env,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let key = (owner, spender);
+    let mut allow = ALLOWANCES
+        .may_load(storage, key)?
+        .ok_or(ContractError::NoAllowance {})?;
+    if allow.expires.is_expired(&env.block) {
+        return Err(ContractError::Expired {});
+    }
+    allow.allowance = allow
+        .allowance
+        .checked_sub(amount)
+        .map_err(|_| ContractError::NoAllowance {})?;
+    if allow.allowance.is_zero() {
+        ALLOWANCES.remove(storage, key);
+    } else {
+        ALLOWANCES.save(storage, key, &allow)?;
+    }
+    Ok(())
+}
+
+fn execute_transfer_from(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner: String,
+    recipient: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let recipient_addr = deps.api.addr_validate(&recipient)?;
+    assert_transfer_allowed(deps.storage, &owner_addr, &recipient_addr)?;
+    deduct_allowance(deps.storage, &owner_addr, &info.sender, &env, amount)?;
+
+    BALANCES.update(
+        deps.storage,
+        &owner_addr,
+        |bal| -> Result<_, ContractError> { Ok(bal.unwrap_or_default().checked_sub(amount)?) },
+    )?;
+    BALANCES.update(
+        deps.storage,
+        &recipient_addr,
+        |bal| -> Result<_, ContractError> { Ok(bal.unwrap_or_default().checked_add(amount)?) },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "transfer_from")
+        .add_attribute("from", owner)
+        .add_attribute("to", recipient)
+        .add_attribute("by", info.sender)
+        .add_attribute("amount", amount))
+}
+
+fn execute_burn_from(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    deduct_allowance(deps.storage, &owner_addr, &info.sender, &env, amount)?;
+
+    BALANCES.update(
+        deps.storage,
+        &owner_addr,
+        |bal| -> Result<_, ContractError> { Ok(bal.unwrap_or_default().checked_sub(amount)?) },
+    )?;
+    TOKEN_INFO.update(deps.storage, |mut token_info| -> Result<_, ContractError> {
+        token_info.total_supply = token_info.total_supply.checked_sub(amount)?;
+        Ok(token_info)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "burn_from")
+        .add_attribute("from", owner)
+        .add_attribute("by", info.sender)
+        .add_attribute("amount", amount))
+}
+
+fn execute_send_from(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner: String,
+    contract: String,
+    amount: Uint128,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let contract_addr = deps.api.addr_validate(&contract)?;
+    assert_transfer_allowed(deps.storage, &owner_addr, &contract_addr)?;
+    deduct_allowance(deps.storage, &owner_addr, &info.sender, &env, amount)?;
+
+    BALANCES.update(
+        deps.storage,
+        &owner_addr,
+        |bal| -> Result<_, ContractError> { Ok(bal.unwrap_or_default().checked_sub(amount)?) },
+    )?;
+    BALANCES.update(
+        deps.storage,
+        &contract_addr,
+        |bal| -> Result<_, ContractError> { Ok(bal.unwrap_or_default().checked_add(amount)?) },
+    )?;
+
+    let send = Cw20ReceiveMsg {
+        sender: info.sender.to_string(),
+        amount,
+        msg,
+    };
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: contract.clone(),
+            msg: to_binary(&send)?,
+            funds: vec![],
+        })
+        .add_attribute("action", "send_from")
+        .add_attribute("from", owner)
+        .add_attribute("to", contract)
+        .add_attribute("by", info.sender)
+        .add_attribute("amount", amount))
+}
+
+fn execute_update_marketing(
+    deps: DepsMut,
+    info: MessageInfo,
+    project: Option<String>,
+    description: Option<String>,
+    marketing: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut info_resp = MARKETING_INFO.may_load(deps.storage)?.unwrap_or_default();
+
+    if info_resp.marketing.as_deref() != Some(info.sender.as_str()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(project) = project {
+        info_resp.project = Some(project);
+    }
+    if let Some(description) = description {
+        info_resp.description = Some(description);
+    }
+    if let Some(marketing) = marketing {
+        info_resp.marketing = Some(deps.api.addr_validate(&marketing)?.to_string());
+    }
+
+    if info_resp.project.is_none()
+        && info_resp.description.is_none()
+        && info_resp.marketing.is_none()
+        && info_resp.logo.is_none()
+    {
+        MARKETING_INFO.remove(deps.storage);
+    } else {
+        MARKETING_INFO.save(deps.storage, &info_resp)?;
+    }
+
+    Ok(Response::new().add_attribute("action", "update_marketing"))
+}
+
+fn execute_upload_logo(
+    deps: DepsMut,
+    info: MessageInfo,
+    logo: Logo,
+) -> Result<Response, ContractError> {
+    let mut info_resp = MARKETING_INFO.may_load(deps.storage)?.unwrap_or_default();
+    if info_resp.marketing.as_deref() != Some(info.sender.as_str()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    verify_logo_size(&logo)?;
+    LOGO.save(deps.storage, &logo)?;
+
+    let logo_info = match logo {
+        Logo::Url(url) => LogoInfo::Url(url),
+        Logo::Embedded(_) => LogoInfo::Embedded,
+    };
+    info_resp.logo = Some(logo_info);
+    MARKETING_INFO.save(deps.storage, &info_resp)?;
+
+    Ok(Response::new().add_attribute("action", "upload_logo"))
+}
+
+fn verify_logo_size(logo: &Logo) -> Result<(), ContractError> {
+    if let Logo::Embedded(embedded) = logo {
+        let len = match embedded {
+            cw20::EmbeddedLogo::Svg(data) => data.len(),
+            cw20::EmbeddedLogo::Png(data) => data.len(),
+        };
+        if len > LOGO_SIZE_CAP {
+            return Err(ContractError::LogoTooBig {});
+        }
+    }
+    Ok(())
+}
+
+fn execute_add_to_allowlist(
+    deps: DepsMut,
+    info: MessageInfo,
+    addresses: Vec<String>,
+) -> Result<Response, ContractError> {
+    TRANSFER_RESTRICTION_ADMIN
+        .get(deps.storage)?
+        .ok_or(ContractError::NoTransferRestriction {})?;
+    TRANSFER_RESTRICTION_ADMIN.assert_admin(deps.storage, &info.sender)?;
+
+    for address in &addresses {
+        let addr = deps.api.addr_validate(address)?;
+        ALLOWLIST.save(deps.storage, &addr, &())?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "add_to_allowlist")
+        .add_attribute("count", addresses.len().to_string()))
+}
+
+fn execute_remove_from_allowlist(
+    deps: DepsMut,
+    info: MessageInfo,
+    addresses: Vec<String>,
+) -> Result<Response, ContractError> {
+    TRANSFER_RESTRICTION_ADMIN
+        .get(deps.storage)?
+        .ok_or(ContractError::NoTransferRestriction {})?;
+    TRANSFER_RESTRICTION_ADMIN.assert_admin(deps.storage, &info.sender)?;
+
+    for address in &addresses {
+        let addr = deps.api.addr_validate(address)?;
+        ALLOWLIST.remove(deps.storage, &addr);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_from_allowlist")
+        .add_attribute("count", addresses.len().to_string()))
+}
+
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Balance { address } => to_binary(&query_balance(deps, address)?),
+        QueryMsg::TokenInfo {} => to_binary(&query_token_info(deps)?),
+        QueryMsg::Minter {} => to_binary(&query_minter(deps)?),
+        QueryMsg::Allowance { owner, spender } => {
+            to_binary(&query_allowance(deps, owner, spender)?)
+        }
+        QueryMsg::AllAllowances {
+            owner,
+            start_after,
+            limit,
+        } => to_binary(&query_all_allowances(deps, owner, start_after, limit)?),
+        QueryMsg::AllSpenderAllowances {
+            spender,
+            start_after,
+            limit,
+        } => to_binary(&query_all_spender_allowances(
+            deps,
+            spender,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::AllAccounts { start_after, limit } => {
+            to_binary(&query_all_accounts(deps, start_after, limit)?)
+        }
+        QueryMsg::MarketingInfo {} => to_binary(&query_marketing_info(deps)?),
+        QueryMsg::DownloadLogo {} => to_binary(&query_download_logo(deps)?),
+        QueryMsg::IsTransferAllowed { from, to } => {
+            to_binary(&query_is_transfer_allowed(deps, from, to)?)
+        }
+    }
+}
+
+fn query_balance(deps: Deps, address: String) -> StdResult<BalanceResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let balance = BALANCES.may_load(deps.storage, &addr)?.unwrap_or_default();
+    Ok(BalanceResponse { balance })
+}
+
+fn query_token_info(deps: Deps) -> StdResult<TokenInfoResponse> {
+    let info = TOKEN_INFO.load(deps.storage)?;
+    Ok(TokenInfoResponse {
+        name: info.name,
+        symbol: info.symbol,
+        decimals: info.decimals,
+        total_supply: info.total_supply,
+    })
+}
+
+fn query_minter(deps: Deps) -> StdResult<Option<MinterResponse>> {
+    let info = TOKEN_INFO.load(deps.storage)?;
+    Ok(info.mint.map(|m| MinterResponse {
+        minter: m.minter.to_string(),
+        cap: m.cap,
+    }))
+}
+
+fn query_allowance(deps: Deps, owner: String, spender: String) -> StdResult<AllowanceResponse> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let spender_addr = deps.api.addr_validate(&spender)?;
+    let allow = ALLOWANCES
+        .may_load(deps.storage, (&owner_addr, &spender_addr))?
+        .unwrap_or_default();
+    Ok(allow)
+}
+
+fn query_all_allowances(
+    deps: Deps,
+    owner: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AllAllowancesResponse> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .map(|s| deps.api.addr_validate(&s))
+        .transpose()?
+        .map(Bound::exclusive);
+
+    let allowances = ALLOWANCES
+        .prefix(&owner_addr)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (spender, allow) = item?;
+            Ok(AllowanceInfo {
+                spender: spender.to_string(),
+                allowance: allow.allowance,
+                expires: allow.expires,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(AllAllowancesResponse { allowances })
+}
+
+// ALLOWANCES is keyed (owner, spender), so a by-spender query can't use a Map prefix;
+// it scans in owner order and filters by spender instead.
+fn query_all_spender_allowances(
+    deps: Deps,
+    spender: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AllSpenderAllowancesResponse> {
+    let spender_addr = deps.api.addr_validate(&spender)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|s| deps.api.addr_validate(&s))
+        .transpose()?;
+
+    let allowances = ALLOWANCES
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|item| {
+            item.as_ref()
+                .map(|((owner, s), _)| {
+                    s == &spender_addr && start_after.as_ref().map_or(true, |after| owner > after)
+                })
+                .unwrap_or(true)
+        })
+        .take(limit)
+        .map(|item| {
+            let ((owner, _), allow) = item?;
+            Ok(SpenderAllowanceInfo {
+                owner: owner.to_string(),
+                allowance: allow.allowance,
+                expires: allow.expires,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(AllSpenderAllowancesResponse { allowances })
+}
+
+fn query_all_accounts(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AllAccountsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .map(|s| deps.api.addr_validate(&s))
+        .transpose()?
+        .map(Bound::exclusive);
+
+    let accounts = BALANCES
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|addr| Ok(addr?.to_string()))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(AllAccountsResponse { accounts })
+}
+
+fn query_marketing_info(deps: Deps) -> StdResult<MarketingInfoResponse> {
+    Ok(MARKETING_INFO.may_load(deps.storage)?.unwrap_or_default())
+}
+
+fn query_download_logo(deps: Deps) -> StdResult<DownloadLogoResponse> {
+    let logo = LOGO.load(deps.storage)?;
+    match logo {
+        Logo::Embedded(cw20::EmbeddedLogo::Svg(data)) => Ok(DownloadLogoResponse {
+            mime_type: "image/svg+xml".to_owned(),
+            data,
+        }),
+        Logo::Embedded(cw20::EmbeddedLogo::Png(data)) => Ok(DownloadLogoResponse {
+            mime_type: "image/png".to_owned(),
+            data,
+        }),
+        Logo::Url(_) => Err(StdError::generic_err("no embedded logo data stored")),
+    }
+}
+
+fn query_is_transfer_allowed(
+    deps: Deps,
+    from: String,
+    to: String,
+) -> StdResult<IsTransferAllowedResponse> {
+    let from_addr = deps.api.addr_validate(&from)?;
+    let to_addr = deps.api.addr_validate(&to)?;
+    let allowed = assert_transfer_allowed(deps.storage, &from_addr, &to_addr).is_ok();
+    Ok(IsTransferAllowedResponse { allowed })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cw20::Cw20Coin;
+
+    fn setup_restricted(
+        admin: &str,
+    ) -> cosmwasm_std::OwnedDeps<
+        cosmwasm_std::MemoryStorage,
+        cosmwasm_std::testing::MockApi,
+        cosmwasm_std::testing::MockQuerier,
+    > {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            name: "Restricted Token".to_string(),
+            symbol: "RSTD".to_string(),
+            decimals: 6,
+            initial_balances: vec![
+                Cw20Coin {
+                    address: "alice".to_string(),
+                    amount: Uint128::new(1_000),
+                },
+                Cw20Coin {
+                    address: "bob".to_string(),
+                    amount: Uint128::new(0),
+                },
+            ],
+            mint: None,
+            marketing: None,
+            transfer_restriction: Some(crate::msg::TransferRestrictionConfig {
+                admin: admin.to_string(),
+            }),
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+        deps
+    }
+
+    #[test]
+    fn transfer_requires_both_parties_allowlisted() {
+        let mut deps = setup_restricted("admin");
+
+        // neither party is allowlisted yet: transfer is rejected
+        let err = execute_transfer(
+            deps.as_mut(),
+            mock_info("alice", &[]),
+            "bob".to_string(),
+            Uint128::new(100),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::NotAllowlisted { .. }));
+
+        // allowlisting only the sender still isn't enough
+        execute_add_to_allowlist(
+            deps.as_mut(),
+            mock_info("admin", &[]),
+            vec!["alice".to_string()],
+        )
+        .unwrap();
+        let err = execute_transfer(
+            deps.as_mut(),
+            mock_info("alice", &[]),
+            "bob".to_string(),
+            Uint128::new(100),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::NotAllowlisted { .. }));
+
+        // once both are allowlisted, transfer succeeds
+        execute_add_to_allowlist(
+            deps.as_mut(),
+            mock_info("admin", &[]),
+            vec!["bob".to_string()],
+        )
+        .unwrap();
+        execute_transfer(
+            deps.as_mut(),
+            mock_info("alice", &[]),
+            "bob".to_string(),
+            Uint128::new(100),
+        )
+        .unwrap();
+        let bob_balance = query_balance(deps.as_ref(), "bob".to_string()).unwrap();
+        assert_eq!(bob_balance.balance, Uint128::new(100));
+
+        // removing a party from the allowlist blocks further transfers between them
+        execute_remove_from_allowlist(
+            deps.as_mut(),
+            mock_info("admin", &[]),
+            vec!["bob".to_string()],
+        )
+        .unwrap();
+        let err = execute_transfer(
+            deps.as_mut(),
+            mock_info("alice", &[]),
+            "bob".to_string(),
+            Uint128::new(1),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::NotAllowlisted { .. }));
+    }
+
+    #[test]
+    fn mint_and_burn_are_not_gated_by_allowlist() {
+        let mut deps = setup_restricted("admin");
+
+        // burn has no counterparty and must succeed despite neither being allowlisted
+        execute_burn(deps.as_mut(), mock_info("alice", &[]), Uint128::new(100)).unwrap();
+        let alice_balance = query_balance(deps.as_ref(), "alice".to_string()).unwrap();
+        assert_eq!(alice_balance.balance, Uint128::new(900));
+
+        // mint to a recipient that isn't allowlisted must succeed as well
+        TOKEN_INFO
+            .update(deps.as_mut().storage, |mut info| -> StdResult<_> {
+                info.mint = Some(MinterData {
+                    minter: Addr::unchecked("minter"),
+                    cap: None,
+                });
+                Ok(info)
+            })
+            .unwrap();
+        execute_mint(
+            deps.as_mut(),
+            mock_info("minter", &[]),
+            "bob".to_string(),
+            Uint128::new(50),
+        )
+        .unwrap();
+        let bob_balance = query_balance(deps.as_ref(), "bob".to_string()).unwrap();
+        assert_eq!(bob_balance.balance, Uint128::new(50));
+    }
+
+    #[test]
+    fn allowlist_management_requires_admin() {
+        let mut deps = setup_restricted("admin");
+
+        let err = execute_add_to_allowlist(
+            deps.as_mut(),
+            mock_info("not-admin", &[]),
+            vec!["alice".to_string()],
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Admin(_)));
+
+        let err = execute_remove_from_allowlist(
+            deps.as_mut(),
+            mock_info("not-admin", &[]),
+            vec!["alice".to_string()],
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Admin(_)));
+    }
+
+    #[test]
+    fn allowlist_management_requires_transfer_restriction_configured() {
+        let mut deps = mock_dependencies();
+        let msg = InstantiateMsg {
+            name: "Open Token".to_string(),
+            symbol: "OPEN".to_string(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: "alice".to_string(),
+                amount: Uint128::new(1_000),
+            }],
+            mint: None,
+            marketing: None,
+            transfer_restriction: None,
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+
+        let err = execute_add_to_allowlist(
+            deps.as_mut(),
+            mock_info("anyone", &[]),
+            vec!["alice".to_string()],
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::NoTransferRestriction {}));
+
+        // unrestricted transfers work freely with no allowlist at all
+        execute_transfer(
+            deps.as_mut(),
+            mock_info("alice", &[]),
+            "bob".to_string(),
+            Uint128::new(100),
+        )
+        .unwrap();
+    }
+}
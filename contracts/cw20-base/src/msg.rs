@@ -1,10 +1,8 @@
 use cosmwasm_std::{StdError, StdResult, Uint128};
-use cw20::{Cw20Coin, Logo, MinterResponse};
+use cw20::{Cw20Coin, Cw20ExecuteMsg, Logo, MinterResponse};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-pub use cw20::Cw20ExecuteMsg as ExecuteMsg;
-
 #[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
 pub struct InstantiateMarketingInfo {
     pub project: Option<String>,
@@ -13,6 +11,14 @@ pub struct InstantiateMarketingInfo {
     pub logo: Option<Logo>,
 }
 
+/// Opt-in config gating transfers to an allowlist of counterparties, the way the
+/// abstract-os token restricts transfers to sanctioned contract instances.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct TransferRestrictionConfig {
+    /// admin allowed to manage the allowlist via `AddToAllowlist`/`RemoveFromAllowlist`
+    pub admin: String,
+}
+
 #[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
 #[cfg_attr(test, derive(Default))]
 pub struct InstantiateMsg {
@@ -22,6 +28,8 @@ pub struct InstantiateMsg {
     pub initial_balances: Vec<Cw20Coin>,
     pub mint: Option<MinterResponse>,
     pub marketing: Option<InstantiateMarketingInfo>,
+    /// enables allowlist-gated transfers; omit to keep the token freely transferable
+    pub transfer_restriction: Option<TransferRestrictionConfig>,
 }
 
 impl InstantiateMsg {
@@ -73,6 +81,32 @@ impl InstantiateMsg {
     }
 }
 
+/// Allowlist-management variants needed for restricted-transfer mode. Kept as its
+/// own tagged enum, rather than spliced directly into `ExecuteMsg`, so that `ExecuteMsg`
+/// can stay an untagged wrapper around `cw20::Cw20ExecuteMsg` without these two variants
+/// losing their `{"add_to_allowlist": {...}}`-style tag.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AllowlistExecuteMsg {
+    /// Only with transfer_restriction set. Admin-gated: allows the given addresses to be a
+    /// transfer counterparty (sender or recipient of Transfer/TransferFrom/Send/SendFrom)
+    AddToAllowlist { addresses: Vec<String> },
+    /// Only with transfer_restriction set. Admin-gated: revokes transfer counterparty status
+    RemoveFromAllowlist { addresses: Vec<String> },
+}
+
+/// Wraps the upstream `cw20::Cw20ExecuteMsg` instead of forking its variants, so this
+/// contract can't silently drift from the real `cw20` crate on its next version bump.
+/// `#[serde(untagged)]` tries each variant in turn, so wire format is unchanged for the
+/// base cw20 messages (e.g. `{"transfer": {...}}`) and `AllowlistExecuteMsg`'s own tag
+/// covers the two added variants (e.g. `{"add_to_allowlist": {...}}`).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum ExecuteMsg {
+    Base(Cw20ExecuteMsg),
+    Allowlist(AllowlistExecuteMsg),
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
@@ -126,6 +160,15 @@ pub enum QueryMsg {
     /// contract.
     /// Return type: DownloadLogoResponse.
     DownloadLogo {},
+    /// Only with transfer_restriction set. Returns whether `from` may currently transfer
+    /// to `to` under the allowlist.
+    /// Return type: IsTransferAllowedResponse.
+    IsTransferAllowed { from: String, to: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct IsTransferAllowedResponse {
+    pub allowed: bool,
 }
 
 #[derive(Serialize, Deserialize, JsonSchema)]
@@ -0,0 +1,41 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Uint128};
+use cw20::{AllowanceResponse, Logo, MarketingInfoResponse};
+use cw_controllers::Admin;
+use cw_storage_plus::{Item, Map};
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MinterData {
+    pub minter: Addr,
+    /// cap is a hard cap on total_supply that can be achieved by minting. Note that
+    /// this refers to total_supply. If you finish all the allowed amount but 50% is
+    /// burned, you can mint again.
+    pub cap: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct TokenInfo {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub total_supply: Uint128,
+    pub mint: Option<MinterData>,
+}
+
+pub const TOKEN_INFO: Item<TokenInfo> = Item::new("token_info");
+pub const BALANCES: Map<&Addr, Uint128> = Map::new("balance");
+pub const ALLOWANCES: Map<(&Addr, &Addr), AllowanceResponse> = Map::new("allowance");
+
+pub const MARKETING_INFO: Item<MarketingInfoResponse> = Item::new("marketing_info");
+pub const LOGO: Item<Logo> = Item::new("logo");
+
+// ADMIN: Item< Option<Addr> >      struct Admin(Item<Option<Addr>>)
+// Manages the allowlist when transfer_restriction is enabled; unset when it isn't.
+pub const TRANSFER_RESTRICTION_ADMIN: Admin = Admin::new("transfer_restriction_admin");
+
+// Addresses allowed as a transfer counterparty (sender or recipient) once
+// transfer_restriction is enabled. Presence in the map means "allowed"; mint/burn
+// are not gated by this allowlist.
+pub const ALLOWLIST: Map<&Addr, ()> = Map::new("allowlist");
@@ -0,0 +1,1234 @@
+use std::collections::BTreeMap;
+
+use cosmwasm_std::{
+    to_binary, Addr, BankMsg, Binary, BlockInfo, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env,
+    MessageInfo, Order, Response, StdError, StdResult, Storage, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, Denom};
+use cw4::{Member, MemberListResponse, MemberResponse, TotalWeightResponse};
+use cw_storage_plus::Bound;
+use cw_utils::{Duration, Expiration};
+
+use crate::error::ContractError;
+use crate::msg::{
+    ClaimableAtResponse, ClaimsResponse, ExecuteMsg, InstantiateMsg, LockPositionResponse,
+    LockedPositionsResponse, PendingRewardsResponse, QueryMsg, ReceiveMsg, StakedByDenomResponse,
+    StakedResponse,
+};
+use crate::state::{
+    Claim, Config, LockConfig, LockPosition, LockTier, StakeConfig, ADMIN, CLAIMS, CONFIG,
+    GLOBAL_REWARD_INDEX, HOLDERS, HOOKS, LOCKS, LOCK_SEQNS, MEMBERS, STAKE, TOTAL,
+    UNDISTRIBUTED_REWARDS,
+};
+
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+/// canonical storage-key form of a `Denom`, used wherever a denom needs to key a Map
+fn denom_key(denom: &Denom) -> String {
+    match denom {
+        Denom::Native(d) => d.clone(),
+        Denom::Cw20(addr) => addr.to_string(),
+    }
+}
+
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let admin = msg.admin.map(|a| deps.api.addr_validate(&a)).transpose()?;
+    ADMIN.set(deps.storage, admin)?;
+
+    let config = Config {
+        denoms: msg
+            .denoms
+            .into_iter()
+            .map(|d| StakeConfig {
+                denom: d.denom,
+                tokens_per_weight: d.tokens_per_weight,
+                min_bond: d.min_bond,
+            })
+            .collect(),
+        unbonding_period: msg.unbonding_period,
+        reward_denom: msg.reward_denom,
+        lock_config: msg.lock_tiers.map(|tiers| LockConfig {
+            tiers: tiers
+                .into_iter()
+                .map(|t| LockTier {
+                    duration: t.duration,
+                    multiplier: t.multiplier,
+                })
+                .collect(),
+        }),
+    };
+    CONFIG.save(deps.storage, &config)?;
+    TOTAL.save(deps.storage, &0)?;
+    GLOBAL_REWARD_INDEX.save(deps.storage, &Decimal::zero())?;
+    UNDISTRIBUTED_REWARDS.save(deps.storage, &Uint128::zero())?;
+
+    Ok(Response::new())
+}
+
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Bond { lock_duration } => execute_bond(deps, env, info, lock_duration),
+        ExecuteMsg::Unbond { denom, tokens } => execute_unbond(deps, env, info, denom, tokens),
+        ExecuteMsg::Claim { max_claims } => execute_claim(deps, env, info, max_claims),
+        ExecuteMsg::Extend {
+            position_id,
+            new_lock,
+        } => execute_extend(deps, env, info, position_id, new_lock),
+        ExecuteMsg::DistributeRewards {} => execute_distribute_rewards(deps, info),
+        ExecuteMsg::ClaimRewards {} => execute_claim_rewards(deps, info),
+        ExecuteMsg::UpdateAdmin { admin } => {
+            let new_admin = admin.map(|a| deps.api.addr_validate(&a)).transpose()?;
+            Ok(ADMIN.execute_update_admin(deps, info, new_admin)?)
+        }
+        ExecuteMsg::AddHook { addr } => {
+            let hook = deps.api.addr_validate(&addr)?;
+            Ok(HOOKS.execute_add_hook(&ADMIN, deps, info, hook)?)
+        }
+        ExecuteMsg::RemoveHook { addr } => {
+            let hook = deps.api.addr_validate(&addr)?;
+            Ok(HOOKS.execute_remove_hook(&ADMIN, deps, info, hook)?)
+        }
+        ExecuteMsg::Receive(wrapper) => execute_receive(deps, env, info, wrapper),
+    }
+}
+
+/// Adds `amount` of `denom` to the sender's stake, creating a `LockPosition` (and
+/// refusing below-`min_bond` first deposits) but without touching membership weight;
+/// the caller settles and recomputes weight once, after all denoms in a call are bonded.
+fn bond_one_denom(
+    storage: &mut dyn Storage,
+    env: &Env,
+    sender: &Addr,
+    denom: &Denom,
+    amount: Uint128,
+    lock_duration: Option<Duration>,
+) -> Result<(), ContractError> {
+    let config = CONFIG.load(storage)?;
+    let stake_cfg = config
+        .denoms
+        .iter()
+        .find(|d| &d.denom == denom)
+        .cloned()
+        .ok_or_else(|| ContractError::UnrecognizedDenom {
+            denom: denom_key(denom),
+        })?;
+
+    let key = denom_key(denom);
+    let prior = STAKE
+        .may_load(storage, (sender, key.clone()))?
+        .unwrap_or_default();
+    if prior.is_zero() && amount < stake_cfg.min_bond {
+        return Err(ContractError::BelowMinBond {
+            min_bond: stake_cfg.min_bond,
+        });
+    }
+    STAKE.save(storage, (sender, key.clone()), &(prior + amount))?;
+
+    if let Some(duration) = lock_duration {
+        let tier = config
+            .lock_config
+            .as_ref()
+            .and_then(|lc| lc.tiers.iter().find(|t| t.duration == duration))
+            .ok_or(ContractError::NoSuchLockTier {})?
+            .clone();
+        if stake_cfg.tokens_per_weight.is_zero() {
+            return Err(ContractError::ZeroTokensPerWeight { denom: key });
+        }
+        let base_weight = (amount.u128() / stake_cfg.tokens_per_weight.u128()) as u64;
+        let position_id = LOCK_SEQNS.may_load(storage, sender)?.unwrap_or_default() + 1;
+        LOCK_SEQNS.save(storage, sender, &position_id)?;
+        LOCKS.save(
+            storage,
+            (sender, position_id),
+            &LockPosition {
+                denom: denom.clone(),
+                amount,
+                base_weight,
+                multiplier: tier.multiplier,
+                locked_until: duration.after(&env.block),
+            },
+        )?;
+    }
+    Ok(())
+}
+
+/// Membership weight = sum over denoms of `unlocked_stake / tokens_per_weight`, plus
+/// the boosted weight (`base_weight * multiplier`) of every still-active lock position.
+/// Locked stake is excluded from the plain per-denom division so it isn't counted twice.
+fn compute_weight(storage: &dyn Storage, block: &BlockInfo, addr: &Addr) -> StdResult<u64> {
+    let config = CONFIG.load(storage)?;
+
+    let mut locked_by_denom: BTreeMap<String, Uint128> = BTreeMap::new();
+    let mut boosted_weight: u128 = 0;
+    for item in LOCKS
+        .prefix(addr)
+        .range(storage, None, None, Order::Ascending)
+    {
+        let (_, pos) = item?;
+        if !pos.locked_until.is_expired(block) {
+            *locked_by_denom.entry(denom_key(&pos.denom)).or_default() += pos.amount;
+            boosted_weight += (pos.multiplier * Uint128::from(pos.base_weight)).u128();
+        }
+    }
+
+    let mut weight = boosted_weight;
+    for sc in &config.denoms {
+        let key = denom_key(&sc.denom);
+        let staked = STAKE
+            .may_load(storage, (addr, key.clone()))?
+            .unwrap_or_default();
+        let locked = locked_by_denom.get(&key).copied().unwrap_or_default();
+        let unlocked = staked.saturating_sub(locked);
+        if !sc.tokens_per_weight.is_zero() {
+            weight += unlocked.u128() / sc.tokens_per_weight.u128();
+        }
+    }
+    Ok(weight.min(u64::MAX as u128) as u64)
+}
+
+/// Sum of `addr`'s still-active (not yet `locked_until`-expired) lock positions for the
+/// denom keyed by `key`, excluding `exclude_position_id` (so a position can be compared
+/// against the stake available to itself while re-locking). `STAKE` holds the raw total
+/// per denom with no reserved sub-accounting of its own, so unbonding must subtract this
+/// out itself.
+fn locked_amount(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    addr: &Addr,
+    key: &str,
+    exclude_position_id: Option<u64>,
+) -> StdResult<Uint128> {
+    let mut locked = Uint128::zero();
+    for item in LOCKS
+        .prefix(addr)
+        .range(storage, None, None, Order::Ascending)
+    {
+        let (position_id, pos) = item?;
+        if Some(position_id) == exclude_position_id {
+            continue;
+        }
+        if denom_key(&pos.denom) == key && !pos.locked_until.is_expired(block) {
+            locked += pos.amount;
+        }
+    }
+    Ok(locked)
+}
+
+/// Settles `addr`'s accrued rewards using its weight *before* this call's bond/unbond
+/// change, then recomputes and stores the new weight (and adjusts `TOTAL` to match).
+fn update_member_weight(deps: DepsMut, env: &Env, addr: &Addr) -> Result<(), ContractError> {
+    let old_weight = MEMBERS.may_load(deps.storage, addr)?.unwrap_or_default();
+    settle_rewards(deps.storage, addr, old_weight)?;
+
+    let new_weight = compute_weight(deps.storage, &env.block, addr)?;
+    if new_weight != old_weight {
+        if new_weight == 0 {
+            MEMBERS.remove(deps.storage, addr, env.block.height)?;
+        } else {
+            MEMBERS.save(deps.storage, addr, &new_weight, env.block.height)?;
+        }
+        TOTAL.update(deps.storage, |total| -> StdResult<_> {
+            Ok(if new_weight >= old_weight {
+                total + (new_weight - old_weight)
+            } else {
+                total - (old_weight - new_weight)
+            })
+        })?;
+    }
+    Ok(())
+}
+
+/// pending += (GLOBAL_REWARD_INDEX - holder.reward_index) * weight; holder.reward_index
+/// is then advanced to the current index, per the global-index accrual design.
+fn settle_rewards(storage: &mut dyn Storage, addr: &Addr, weight: u64) -> StdResult<()> {
+    let global_index = GLOBAL_REWARD_INDEX.load(storage)?;
+    let mut holder = HOLDERS.may_load(storage, addr)?.unwrap_or_default();
+    if weight > 0 {
+        let delta_index = global_index - holder.reward_index;
+        holder.pending_rewards += delta_index * Decimal::from_ratio(weight, 1u128);
+    }
+    holder.reward_index = global_index;
+    HOLDERS.save(storage, addr, &holder)?;
+    Ok(())
+}
+
+fn reward_payout_msg(denom: &Denom, recipient: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(match denom {
+        Denom::Native(d) => BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin {
+                denom: d.clone(),
+                amount,
+            }],
+        }
+        .into(),
+        Denom::Cw20(addr) => WasmMsg::Execute {
+            contract_addr: addr.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+    })
+}
+
+fn execute_bond(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lock_duration: Option<Duration>,
+) -> Result<Response, ContractError> {
+    if info.funds.is_empty() {
+        return Err(ContractError::NoFunds {});
+    }
+    for coin in &info.funds {
+        bond_one_denom(
+            deps.storage,
+            &env,
+            &info.sender,
+            &Denom::Native(coin.denom.clone()),
+            coin.amount,
+            lock_duration,
+        )?;
+    }
+    update_member_weight(deps, &env, &info.sender)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "bond")
+        .add_attribute("sender", info.sender))
+}
+
+fn execute_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let msg: ReceiveMsg = cosmwasm_std::from_binary(&wrapper.msg)?;
+    let sender = deps.api.addr_validate(&wrapper.sender)?;
+    let cw20_addr = info.sender;
+
+    match msg {
+        ReceiveMsg::Bond { lock_duration } => {
+            bond_one_denom(
+                deps.storage,
+                &env,
+                &sender,
+                &Denom::Cw20(cw20_addr),
+                wrapper.amount,
+                lock_duration,
+            )?;
+            update_member_weight(deps, &env, &sender)?;
+
+            Ok(Response::new()
+                .add_attribute("action", "bond")
+                .add_attribute("sender", sender))
+        }
+        ReceiveMsg::DistributeRewards {} => {
+            let config = CONFIG.load(deps.storage)?;
+            if config.reward_denom != Denom::Cw20(cw20_addr.clone()) {
+                return Err(ContractError::UnrecognizedDenom {
+                    denom: denom_key(&Denom::Cw20(cw20_addr)),
+                });
+            }
+            if wrapper.amount.is_zero() {
+                return Err(ContractError::NoFunds {});
+            }
+            credit_rewards(deps.storage, wrapper.amount)?;
+
+            Ok(Response::new()
+                .add_attribute("action", "distribute_rewards")
+                .add_attribute("amount", wrapper.amount))
+        }
+    }
+}
+
+/// Sortable stand-in for `Expiration`'s underlying height/time, so claims can be kept
+/// ordered oldest-release-first without `Expiration` itself needing to implement `Ord`.
+/// `Never` sorts last since it can never mature.
+fn expiration_order_key(e: &Expiration) -> u64 {
+    match e {
+        Expiration::AtHeight(h) => *h,
+        Expiration::AtTime(t) => t.nanos(),
+        Expiration::Never {} => u64::MAX,
+    }
+}
+
+/// Adds `amount` to `addr`'s existing claim for `denom`/`release_at` if one is already
+/// pending, else inserts a new one, keeping the Vec sorted oldest-`release_at`-first so
+/// `execute_claim`'s `max_claims` bound processes the longest-waiting claims first.
+fn push_claim(
+    storage: &mut dyn Storage,
+    addr: &Addr,
+    denom: Denom,
+    amount: Uint128,
+    release_at: Expiration,
+) -> StdResult<()> {
+    CLAIMS.update(storage, addr, |claims| -> StdResult<_> {
+        let mut claims = claims.unwrap_or_default();
+        match claims
+            .iter_mut()
+            .find(|c| c.denom == denom && c.release_at == release_at)
+        {
+            Some(existing) => existing.amount += amount,
+            None => {
+                let key = expiration_order_key(&release_at);
+                let idx = claims.partition_point(|c| expiration_order_key(&c.release_at) <= key);
+                claims.insert(
+                    idx,
+                    Claim {
+                        denom,
+                        amount,
+                        release_at,
+                    },
+                );
+            }
+        }
+        Ok(claims)
+    })?;
+    Ok(())
+}
+
+fn execute_unbond(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    denom: Denom,
+    tokens: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if !config.denoms.iter().any(|d| d.denom == denom) {
+        return Err(ContractError::UnrecognizedDenom {
+            denom: denom_key(&denom),
+        });
+    }
+
+    let key = (&info.sender, denom_key(&denom));
+    let prior = STAKE
+        .may_load(deps.storage, key.clone())?
+        .unwrap_or_default();
+    let locked = locked_amount(deps.storage, &env.block, &info.sender, &key.1, None)?;
+    let unlocked = prior.saturating_sub(locked);
+    if unlocked < tokens {
+        return Err(ContractError::InsufficientUnlockedStake {
+            denom: denom_key(&denom),
+        });
+    }
+    let remaining = prior - tokens;
+    if remaining.is_zero() {
+        STAKE.remove(deps.storage, key);
+    } else {
+        STAKE.save(deps.storage, key, &remaining)?;
+    }
+
+    let release_at = config.unbonding_period.after(&env.block);
+    push_claim(deps.storage, &info.sender, denom, tokens, release_at)?;
+
+    update_member_weight(deps, &env, &info.sender)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "unbond")
+        .add_attribute("sender", info.sender)
+        .add_attribute("tokens", tokens))
+}
+
+fn execute_claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    max_claims: Option<u32>,
+) -> Result<Response, ContractError> {
+    let limit = max_claims.map(|m| m as usize).unwrap_or(usize::MAX);
+    let claims = CLAIMS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+
+    // claims are kept oldest-release-first by push_claim, so taking matured ones in
+    // order and stopping at `limit` naturally processes the longest-waiting first
+    let mut matured: Vec<Claim> = Vec::new();
+    let mut pending: Vec<Claim> = Vec::new();
+    for c in claims {
+        if matured.len() < limit && c.release_at.is_expired(&env.block) {
+            matured.push(c);
+        } else {
+            pending.push(c);
+        }
+    }
+
+    if pending.is_empty() {
+        CLAIMS.remove(deps.storage, &info.sender);
+    } else {
+        CLAIMS.save(deps.storage, &info.sender, &pending)?;
+    }
+
+    let total: Uint128 = matured.iter().map(|c| c.amount).sum();
+    if total.is_zero() {
+        return Ok(Response::new()
+            .add_attribute("action", "claim")
+            .add_attribute("tokens", "0"));
+    }
+
+    // a sender can have matured claims in more than one denom at once; pay each out
+    let mut by_denom: Vec<(Denom, Uint128)> = Vec::new();
+    for c in matured {
+        match by_denom.iter_mut().find(|(d, _)| d == &c.denom) {
+            Some((_, amount)) => *amount += c.amount,
+            None => by_denom.push((c.denom, c.amount)),
+        }
+    }
+    let msgs = by_denom
+        .iter()
+        .map(|(denom, amount)| reward_payout_msg(denom, &info.sender, *amount))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "claim")
+        .add_attribute("tokens", total))
+}
+
+fn execute_extend(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    position_id: u64,
+    new_lock: Duration,
+) -> Result<Response, ContractError> {
+    let mut position = LOCKS
+        .may_load(deps.storage, (&info.sender, position_id))?
+        .ok_or(ContractError::NoSuchLockPosition { position_id })?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let tier = config
+        .lock_config
+        .as_ref()
+        .and_then(|lc| lc.tiers.iter().find(|t| t.duration == new_lock))
+        .ok_or(ContractError::NoSuchLockTier {})?
+        .clone();
+
+    // the position's amount/base_weight can be stale if some of its stake was unbonded
+    // after the lock matured; cap it to what's still actually staked and not already
+    // claimed by another active lock before reviving it with a fresh lock.
+    let key = denom_key(&position.denom);
+    let staked = STAKE
+        .may_load(deps.storage, (&info.sender, key.clone()))?
+        .unwrap_or_default();
+    let locked_by_others = locked_amount(
+        deps.storage,
+        &env.block,
+        &info.sender,
+        &key,
+        Some(position_id),
+    )?;
+    let available = staked.saturating_sub(locked_by_others);
+    if available.is_zero() {
+        return Err(ContractError::InsufficientUnlockedStake { denom: key });
+    }
+    position.amount = position.amount.min(available);
+
+    let stake_cfg = config
+        .denoms
+        .iter()
+        .find(|d| d.denom == position.denom)
+        .cloned()
+        .ok_or_else(|| ContractError::UnrecognizedDenom { denom: key.clone() })?;
+    if stake_cfg.tokens_per_weight.is_zero() {
+        return Err(ContractError::ZeroTokensPerWeight { denom: key });
+    }
+    position.base_weight = (position.amount.u128() / stake_cfg.tokens_per_weight.u128()) as u64;
+
+    position.multiplier = tier.multiplier;
+    position.locked_until = new_lock.after(&env.block);
+    LOCKS.save(deps.storage, (&info.sender, position_id), &position)?;
+
+    update_member_weight(deps, &env, &info.sender)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "extend")
+        .add_attribute("position_id", position_id.to_string()))
+}
+
+fn execute_distribute_rewards(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let denom = match &config.reward_denom {
+        Denom::Native(d) => d.clone(),
+        // a cw20 reward_denom is deposited via Receive{DistributeRewards{}} instead,
+        // since cw20 tokens can't be attached to a plain execute as `info.funds`
+        Denom::Cw20(_) => {
+            return Err(ContractError::UnrecognizedDenom {
+                denom: denom_key(&config.reward_denom),
+            })
+        }
+    };
+    let amount = info
+        .funds
+        .iter()
+        .find(|c| c.denom == denom)
+        .map(|c| c.amount)
+        .unwrap_or_default();
+    if amount.is_zero() {
+        return Err(ContractError::NoFunds {});
+    }
+    credit_rewards(deps.storage, amount)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "distribute_rewards")
+        .add_attribute("amount", amount))
+}
+
+/// Folds `amount` of reward tokens into the pool: pro-rata across `TOTAL` weight via
+/// `GLOBAL_REWARD_INDEX` if anyone is staked yet, else buffered in `UNDISTRIBUTED_REWARDS`
+/// for the next deposit that finds weight. Shared by both the native (`DistributeRewards`)
+/// and cw20 (`Receive{DistributeRewards}`) reward-denom deposit paths.
+fn credit_rewards(storage: &mut dyn Storage, amount: Uint128) -> StdResult<()> {
+    let total_weight = TOTAL.load(storage)?;
+    let undistributed = UNDISTRIBUTED_REWARDS.load(storage)?;
+    let pool = undistributed + amount;
+
+    if total_weight == 0 {
+        UNDISTRIBUTED_REWARDS.save(storage, &pool)?;
+    } else {
+        let mut global_index = GLOBAL_REWARD_INDEX.load(storage)?;
+        global_index += Decimal::from_ratio(pool, total_weight);
+        GLOBAL_REWARD_INDEX.save(storage, &global_index)?;
+        UNDISTRIBUTED_REWARDS.save(storage, &Uint128::zero())?;
+    }
+    Ok(())
+}
+
+fn execute_claim_rewards(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let weight = MEMBERS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    settle_rewards(deps.storage, &info.sender, weight)?;
+
+    let mut holder = HOLDERS.load(deps.storage, &info.sender)?;
+    let payout = holder.pending_rewards.to_uint_floor();
+    if payout.is_zero() {
+        return Ok(Response::new()
+            .add_attribute("action", "claim_rewards")
+            .add_attribute("amount", "0"));
+    }
+    holder.pending_rewards -= Decimal::from_ratio(payout, 1u128);
+    HOLDERS.save(deps.storage, &info.sender, &holder)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let msg = reward_payout_msg(&config.reward_denom, &info.sender, payout)?;
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "claim_rewards")
+        .add_attribute("amount", payout))
+}
+
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Claims { address } => {
+            let addr = deps.api.addr_validate(&address)?;
+            let claims = CLAIMS.may_load(deps.storage, &addr)?.unwrap_or_default();
+            to_binary(&ClaimsResponse { claims })
+        }
+        QueryMsg::ClaimableAt { address, at_time } => {
+            to_binary(&query_claimable_at(deps, address, at_time)?)
+        }
+        QueryMsg::Staked { address, denom } => to_binary(&query_staked(deps, address, denom)?),
+        QueryMsg::Admin {} => to_binary(&ADMIN.query_admin(deps)?),
+        QueryMsg::TotalWeight {} => {
+            let weight = TOTAL.load(deps.storage)?;
+            to_binary(&TotalWeightResponse { weight })
+        }
+        QueryMsg::ListMembers { start_after, limit } => {
+            to_binary(&query_list_members(deps, start_after, limit)?)
+        }
+        QueryMsg::Member { addr, at_height } => to_binary(&query_member(deps, addr, at_height)?),
+        QueryMsg::Hooks {} => to_binary(&HOOKS.query_hooks(deps)?),
+        QueryMsg::PendingRewards { address } => to_binary(&query_pending_rewards(deps, address)?),
+        QueryMsg::StakedByDenom { address } => to_binary(&query_staked_by_denom(deps, address)?),
+        QueryMsg::LockedPositions { address } => to_binary(&query_locked_positions(deps, address)?),
+    }
+}
+
+fn claim_matured(release_at: &Expiration, at_time: &Expiration) -> bool {
+    use cw_utils::Expiration::{AtHeight, AtTime, Never};
+    match (release_at, at_time) {
+        (AtHeight(r), AtHeight(a)) => r <= a,
+        (AtTime(r), AtTime(a)) => r <= a,
+        (Never {}, _) | (_, Never {}) => false,
+        _ => false,
+    }
+}
+
+fn query_claimable_at(
+    deps: Deps,
+    address: String,
+    at_time: Expiration,
+) -> StdResult<ClaimableAtResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let claims = CLAIMS.may_load(deps.storage, &addr)?.unwrap_or_default();
+
+    let mut claimable = Uint128::zero();
+    let mut pending = Uint128::zero();
+    for c in claims {
+        if claim_matured(&c.release_at, &at_time) {
+            claimable += c.amount;
+        } else {
+            pending += c.amount;
+        }
+    }
+    Ok(ClaimableAtResponse { claimable, pending })
+}
+
+fn query_staked(deps: Deps, address: String, denom: Option<Denom>) -> StdResult<StakedResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let config = CONFIG.load(deps.storage)?;
+    let stake_cfg = match denom {
+        Some(d) => config
+            .denoms
+            .into_iter()
+            .find(|sc| sc.denom == d)
+            .ok_or_else(|| {
+                StdError::generic_err(format!("unrecognized denom: {}", denom_key(&d)))
+            })?,
+        None => config
+            .denoms
+            .into_iter()
+            .next()
+            .ok_or_else(|| StdError::generic_err("pool has no configured denoms"))?,
+    };
+    let key = denom_key(&stake_cfg.denom);
+    let stake = STAKE
+        .may_load(deps.storage, (&addr, key))?
+        .unwrap_or_default();
+    Ok(StakedResponse {
+        stake,
+        denom: stake_cfg.denom,
+    })
+}
+
+fn query_staked_by_denom(deps: Deps, address: String) -> StdResult<StakedByDenomResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let config = CONFIG.load(deps.storage)?;
+    let stakes = config
+        .denoms
+        .into_iter()
+        .map(|sc| {
+            let key = denom_key(&sc.denom);
+            let stake = STAKE
+                .may_load(deps.storage, (&addr, key))?
+                .unwrap_or_default();
+            Ok(StakedResponse {
+                stake,
+                denom: sc.denom,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(StakedByDenomResponse { stakes })
+}
+
+fn query_pending_rewards(deps: Deps, address: String) -> StdResult<PendingRewardsResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let global_index = GLOBAL_REWARD_INDEX.load(deps.storage)?;
+    let weight = MEMBERS.may_load(deps.storage, &addr)?.unwrap_or_default();
+    let holder = HOLDERS.may_load(deps.storage, &addr)?.unwrap_or_default();
+
+    let accrued = (global_index - holder.reward_index) * Decimal::from_ratio(weight, 1u128);
+    let pending_rewards = (holder.pending_rewards + accrued).to_uint_floor();
+    Ok(PendingRewardsResponse {
+        address,
+        pending_rewards,
+    })
+}
+
+fn query_locked_positions(deps: Deps, address: String) -> StdResult<LockedPositionsResponse> {
+    let addr = deps.api.addr_validate(&address)?;
+    let positions = LOCKS
+        .prefix(&addr)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (position_id, pos) = item?;
+            let weight = (pos.multiplier * Uint128::from(pos.base_weight)).u128() as u64;
+            Ok(LockPositionResponse {
+                position_id,
+                denom: pos.denom,
+                amount: pos.amount,
+                weight,
+                locked_until: pos.locked_until,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(LockedPositionsResponse { positions })
+}
+
+fn query_member(deps: Deps, addr: String, at_height: Option<u64>) -> StdResult<MemberResponse> {
+    let addr = deps.api.addr_validate(&addr)?;
+    let weight = match at_height {
+        Some(h) => MEMBERS.may_load_at_height(deps.storage, &addr, h)?,
+        None => MEMBERS.may_load(deps.storage, &addr)?,
+    };
+    Ok(MemberResponse { weight })
+}
+
+fn query_list_members(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<MemberListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .map(|s| deps.api.addr_validate(&s))
+        .transpose()?
+        .map(Bound::exclusive);
+
+    let members = MEMBERS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (addr, weight) = item?;
+            Ok(Member {
+                addr: addr.to_string(),
+                weight,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(MemberListResponse { members })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{coin, coins, Decimal};
+
+    fn setup_with_reward_denom(
+        reward_denom: Denom,
+        stake_native: &str,
+    ) -> (
+        cosmwasm_std::OwnedDeps<
+            cosmwasm_std::MemoryStorage,
+            cosmwasm_std::testing::MockApi,
+            cosmwasm_std::testing::MockQuerier,
+        >,
+        Env,
+    ) {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let msg = InstantiateMsg {
+            denoms: vec![crate::msg::StakeConfig {
+                denom: Denom::Native(stake_native.to_string()),
+                tokens_per_weight: Uint128::new(100),
+                min_bond: Uint128::new(100),
+            }],
+            unbonding_period: Duration::Height(10),
+            reward_denom,
+            lock_tiers: None,
+            admin: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+        (deps, env)
+    }
+
+    fn setup(
+        reward_native: &str,
+        stake_native: &str,
+    ) -> (
+        cosmwasm_std::OwnedDeps<
+            cosmwasm_std::MemoryStorage,
+            cosmwasm_std::testing::MockApi,
+            cosmwasm_std::testing::MockQuerier,
+        >,
+        Env,
+    ) {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let msg = InstantiateMsg {
+            denoms: vec![crate::msg::StakeConfig {
+                denom: Denom::Native(stake_native.to_string()),
+                tokens_per_weight: Uint128::new(100),
+                min_bond: Uint128::new(100),
+            }],
+            unbonding_period: Duration::Height(10),
+            reward_denom: Denom::Native(reward_native.to_string()),
+            lock_tiers: None,
+            admin: None,
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+        (deps, env)
+    }
+
+    #[test]
+    fn multi_staker_proportional_split() {
+        let (mut deps, env) = setup("reward", "stake");
+
+        execute_bond(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &coins(300, "stake")),
+            None,
+        )
+        .unwrap();
+        execute_bond(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("bob", &coins(100, "stake")),
+            None,
+        )
+        .unwrap();
+        // alice: weight 3, bob: weight 1 -> total weight 4
+
+        execute_distribute_rewards(deps.as_mut(), mock_info("funder", &coins(400, "reward")))
+            .unwrap();
+
+        let alice = query_pending_rewards(deps.as_ref(), "alice".to_string()).unwrap();
+        let bob = query_pending_rewards(deps.as_ref(), "bob".to_string()).unwrap();
+        assert_eq!(alice.pending_rewards, Uint128::new(300));
+        assert_eq!(bob.pending_rewards, Uint128::new(100));
+
+        execute_claim_rewards(deps.as_mut(), mock_info("alice", &[])).unwrap();
+        let alice = query_pending_rewards(deps.as_ref(), "alice".to_string()).unwrap();
+        assert_eq!(alice.pending_rewards, Uint128::zero());
+    }
+
+    #[test]
+    fn zero_weight_rewards_are_buffered() {
+        let (mut deps, env) = setup("reward", "stake");
+
+        // nobody has bonded yet: the deposit has nothing to divide by and must be buffered
+        execute_distribute_rewards(deps.as_mut(), mock_info("funder", &coins(500, "reward")))
+            .unwrap();
+        assert_eq!(
+            UNDISTRIBUTED_REWARDS.load(&deps.storage).unwrap(),
+            Uint128::new(500)
+        );
+        assert_eq!(
+            GLOBAL_REWARD_INDEX.load(&deps.storage).unwrap(),
+            Decimal::zero()
+        );
+
+        execute_bond(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &coins(100, "stake")),
+            None,
+        )
+        .unwrap();
+
+        // next deposit folds the buffered amount in now that weight exists
+        execute_distribute_rewards(deps.as_mut(), mock_info("funder", &[coin(0, "reward")]))
+            .unwrap_err();
+        execute_distribute_rewards(deps.as_mut(), mock_info("funder", &coins(100, "reward")))
+            .unwrap();
+
+        assert_eq!(
+            UNDISTRIBUTED_REWARDS.load(&deps.storage).unwrap(),
+            Uint128::zero()
+        );
+        let alice = query_pending_rewards(deps.as_ref(), "alice".to_string()).unwrap();
+        // 500 buffered + 100 new = 600 distributed over alice's sole weight of 1
+        assert_eq!(alice.pending_rewards, Uint128::new(600));
+    }
+
+    #[test]
+    fn unbond_refuses_to_drain_locked_stake() {
+        let (mut deps, env) = setup("reward", "stake");
+        CONFIG
+            .update(deps.as_mut().storage, |mut c| -> StdResult<_> {
+                c.lock_config = Some(LockConfig {
+                    tiers: vec![LockTier {
+                        duration: Duration::Height(100),
+                        multiplier: Decimal::percent(150),
+                    }],
+                });
+                Ok(c)
+            })
+            .unwrap();
+
+        execute_bond(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &coins(200, "stake")),
+            Some(Duration::Height(100)),
+        )
+        .unwrap();
+
+        // all 200 tokens are locked: unbonding any of it must fail
+        let err = execute_unbond(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &[]),
+            Denom::Native("stake".to_string()),
+            Uint128::new(1),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::InsufficientUnlockedStake { .. }
+        ));
+
+        // once the lock has expired the stake is unlocked and can be unbonded
+        let mut later = env;
+        later.block.height += 200;
+        execute_unbond(
+            deps.as_mut(),
+            later,
+            mock_info("alice", &[]),
+            Denom::Native("stake".to_string()),
+            Uint128::new(200),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn claims_merge_by_release_at_and_claim_is_bounded_oldest_first() {
+        let (mut deps, env) = setup("reward", "stake");
+        execute_bond(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &coins(400, "stake")),
+            None,
+        )
+        .unwrap();
+
+        // two unbonds in the same block share a release_at and must merge into one claim
+        execute_unbond(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &[]),
+            Denom::Native("stake".to_string()),
+            Uint128::new(50),
+        )
+        .unwrap();
+        execute_unbond(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &[]),
+            Denom::Native("stake".to_string()),
+            Uint128::new(50),
+        )
+        .unwrap();
+
+        let claims = CLAIMS
+            .load(&deps.storage, &Addr::unchecked("alice"))
+            .unwrap();
+        assert_eq!(claims.len(), 1);
+        assert_eq!(claims[0].amount, Uint128::new(100));
+
+        // a later unbond has a later release_at and stays a separate, newer claim
+        let mut next_block = env.clone();
+        next_block.block.height += 1;
+        execute_unbond(
+            deps.as_mut(),
+            next_block.clone(),
+            mock_info("alice", &[]),
+            Denom::Native("stake".to_string()),
+            Uint128::new(50),
+        )
+        .unwrap();
+        let claims = CLAIMS
+            .load(&deps.storage, &Addr::unchecked("alice"))
+            .unwrap();
+        assert_eq!(claims.len(), 2);
+
+        // both claims have matured, but max_claims=1 only pays out the older one
+        let mut later = next_block;
+        later.block.height += 20;
+        let res = execute_claim(
+            deps.as_mut(),
+            later.clone(),
+            mock_info("alice", &[]),
+            Some(1),
+        )
+        .unwrap();
+        assert_eq!(
+            res.attributes
+                .iter()
+                .find(|a| a.key == "tokens")
+                .unwrap()
+                .value,
+            "100"
+        );
+        let claims = CLAIMS
+            .load(&deps.storage, &Addr::unchecked("alice"))
+            .unwrap();
+        assert_eq!(claims.len(), 1);
+        assert_eq!(claims[0].amount, Uint128::new(50));
+
+        // claiming again with no bound picks up the rest
+        execute_claim(deps.as_mut(), later, mock_info("alice", &[]), None).unwrap();
+        assert!(CLAIMS
+            .may_load(&deps.storage, &Addr::unchecked("alice"))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn extend_reconciles_stale_amount_against_remaining_stake() {
+        let (mut deps, env) = setup("reward", "stake");
+        CONFIG
+            .update(deps.as_mut().storage, |mut c| -> StdResult<_> {
+                c.lock_config = Some(LockConfig {
+                    tiers: vec![LockTier {
+                        duration: Duration::Height(100),
+                        multiplier: Decimal::percent(200),
+                    }],
+                });
+                Ok(c)
+            })
+            .unwrap();
+
+        execute_bond(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("alice", &coins(200, "stake")),
+            Some(Duration::Height(100)),
+        )
+        .unwrap();
+        let position_id = LOCK_SEQNS
+            .load(&deps.storage, &Addr::unchecked("alice"))
+            .unwrap();
+
+        // let the lock expire, then partially unbond what's now unlocked
+        let mut expired = env.clone();
+        expired.block.height += 100;
+        execute_unbond(
+            deps.as_mut(),
+            expired.clone(),
+            mock_info("alice", &[]),
+            Denom::Native("stake".to_string()),
+            Uint128::new(150),
+        )
+        .unwrap();
+        // 50 unlocked tokens / tokens_per_weight 100 = weight 0, so alice is no longer a member
+        assert_eq!(
+            MEMBERS
+                .may_load(&deps.storage, &Addr::unchecked("alice"))
+                .unwrap(),
+            None
+        );
+
+        // re-locking must reconcile the stale amount=200/base_weight=2 down to the 50
+        // tokens actually still staked, instead of reviving the full original amount
+        execute_extend(
+            deps.as_mut(),
+            expired.clone(),
+            mock_info("alice", &[]),
+            position_id,
+            Duration::Height(100),
+        )
+        .unwrap();
+
+        let position = LOCKS
+            .load(&deps.storage, (&Addr::unchecked("alice"), position_id))
+            .unwrap();
+        assert_eq!(position.amount, Uint128::new(50));
+        assert_eq!(position.base_weight, 0);
+        // weight = boosted (2 * base_weight=0) + unlocked/tokens_per_weight (0/100) = 0
+        assert_eq!(
+            MEMBERS
+                .may_load(&deps.storage, &Addr::unchecked("alice"))
+                .unwrap(),
+            None
+        );
+
+        // and the remaining 50 real tokens are not permanently locked behind the stale
+        // position: once this fresh lock also expires they can be unbonded
+        let mut later = expired;
+        later.block.height += 100;
+        execute_unbond(
+            deps.as_mut(),
+            later,
+            mock_info("alice", &[]),
+            Denom::Native("stake".to_string()),
+            Uint128::new(50),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn cw20_reward_denom_distributes_via_receive() {
+        let reward_cw20 = Addr::unchecked("reward-token");
+        let (mut deps, env) = setup_with_reward_denom(Denom::Cw20(reward_cw20.clone()), "stake");
+
+        execute_bond(
+            deps.as_mut(),
+            env,
+            mock_info("alice", &coins(100, "stake")),
+            None,
+        )
+        .unwrap();
+
+        // DistributeRewards{} (the native path) must reject a cw20 reward_denom pool
+        let err = execute_distribute_rewards(deps.as_mut(), mock_info("funder", &[])).unwrap_err();
+        assert!(matches!(err, ContractError::UnrecognizedDenom { .. }));
+
+        // depositing via Receive from the configured reward cw20 credits the pool
+        let wrapper = Cw20ReceiveMsg {
+            sender: "funder".to_string(),
+            amount: Uint128::new(500),
+            msg: to_binary(&ReceiveMsg::DistributeRewards {}).unwrap(),
+        };
+        execute_receive(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(reward_cw20.as_str(), &[]),
+            wrapper,
+        )
+        .unwrap();
+
+        let alice = query_pending_rewards(deps.as_ref(), "alice".to_string()).unwrap();
+        assert_eq!(alice.pending_rewards, Uint128::new(500));
+
+        // a deposit claiming to come from some other cw20 contract must be rejected
+        let other_wrapper = Cw20ReceiveMsg {
+            sender: "funder".to_string(),
+            amount: Uint128::new(500),
+            msg: to_binary(&ReceiveMsg::DistributeRewards {}).unwrap(),
+        };
+        let err = execute_receive(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("some-other-cw20", &[]),
+            other_wrapper,
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::UnrecognizedDenom { .. }));
+    }
+
+    #[test]
+    fn bond_with_lock_rejects_zero_tokens_per_weight() {
+        let (mut deps, env) = setup("reward", "stake");
+        CONFIG
+            .update(deps.as_mut().storage, |mut c| -> StdResult<_> {
+                c.denoms[0].tokens_per_weight = Uint128::zero();
+                c.lock_config = Some(LockConfig {
+                    tiers: vec![LockTier {
+                        duration: Duration::Height(100),
+                        multiplier: Decimal::percent(150),
+                    }],
+                });
+                Ok(c)
+            })
+            .unwrap();
+
+        let err = execute_bond(
+            deps.as_mut(),
+            env,
+            mock_info("alice", &coins(200, "stake")),
+            Some(Duration::Height(100)),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::ZeroTokensPerWeight { .. }));
+    }
+}
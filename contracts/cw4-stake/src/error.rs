@@ -0,0 +1,40 @@
+use cosmwasm_std::{StdError, Uint128};
+use cw_controllers::{AdminError, HookError};
+use cw_utils::PaymentError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("{0}")]
+    Admin(#[from] AdminError),
+
+    #[error("{0}")]
+    Hook(#[from] HookError),
+
+    #[error("No funds sent")]
+    NoFunds {},
+
+    #[error("Funds of denom '{denom}' are not accepted by this pool")]
+    UnrecognizedDenom { denom: String },
+
+    #[error("Must send at least {min_bond} tokens to bond")]
+    BelowMinBond { min_bond: Uint128 },
+
+    #[error("Not enough unlocked stake of denom '{denom}' to unbond that amount")]
+    InsufficientUnlockedStake { denom: String },
+
+    #[error("Lock position {position_id} not found")]
+    NoSuchLockPosition { position_id: u64 },
+
+    #[error("No lock tier matches the requested duration")]
+    NoSuchLockTier {},
+
+    #[error("Pool for denom '{denom}' has tokens_per_weight=0 and cannot convert stake to weight")]
+    ZeroTokensPerWeight { denom: String },
+}
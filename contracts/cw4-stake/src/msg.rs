@@ -1,19 +1,39 @@
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Decimal, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use cw20::{Cw20ReceiveMsg, Denom};
-pub use cw_controllers::ClaimsResponse;
-use cw_utils::Duration;
+use cw_utils::{Duration, Expiration};
 
+pub use crate::state::{Claim, ClaimsResponse};
+
+/// One whitelisted stakeable denom and its conversion rate into membership weight
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
-pub struct InstantiateMsg {
-    /// denom of the token to stake
+pub struct StakeConfig {
     pub denom: Denom,
     pub tokens_per_weight: Uint128,
     pub min_bond: Uint128,
+}
+
+/// One voluntary-lockup tier offered at bond time, e.g. 90 days -> 1.5x weight
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct LockTier {
+    pub duration: Duration,
+    pub multiplier: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct InstantiateMsg {
+    /// denoms accepted for staking. Single-denom pools are simply a one-element Vec.
+    pub denoms: Vec<StakeConfig>,
     pub unbonding_period: Duration,
 
+    /// denom that `DistributeRewards` accepts and that `ClaimRewards` pays out
+    pub reward_denom: Denom,
+
+    /// lock tiers available to bonders for a weight boost; omit to disable locking
+    pub lock_tiers: Option<Vec<LockTier>>,
+
     // admin can only add/remove hooks, not change other parameters
     pub admin: Option<String>,
 }
@@ -21,15 +41,35 @@ pub struct InstantiateMsg {
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    /// Bond will bond all staking tokens sent with the message and update membership weight
-    Bond {},
-    /// Unbond will start the unbonding process for the given number of tokens.
-    /// The sender immediately loses weight from these tokens, and can claim them
-    /// back to his wallet after `unbonding_period`
-    Unbond { tokens: Uint128 },
+    /// Bond will bond all staking tokens sent with the message (for the denom(s) they
+    /// are in) and update membership weight. With `lock_duration` set, it must match one
+    /// of the configured `LockTier`s (else the bond fails with `NoSuchLockTier`); the
+    /// bonded amount is then locked until it elapses and its weight is boosted by that
+    /// tier's multiplier. Without it, the amount bonds as normal, unlocked stake.
+    Bond { lock_duration: Option<Duration> },
+    /// Unbond will start the unbonding process for the given number of tokens of the
+    /// given denom. The sender immediately loses weight from these tokens, and can
+    /// claim them back to his wallet after `unbonding_period`. Fails if the tokens
+    /// are still within an active lock.
+    Unbond { denom: Denom, tokens: Uint128 },
     /// Claim is used to claim your native tokens that you previously "unbonded"
-    /// after the contract-defined waiting period (eg. 1 week)
-    Claim {},
+    /// after the contract-defined waiting period (eg. 1 week). Processes matured
+    /// claims oldest-first; `max_claims` bounds how many are paid out in this call
+    /// so heavy unbonders don't blow the gas limit, defaulting to "all of them".
+    Claim { max_claims: Option<u32> },
+
+    /// Re-locks an existing lock position for a new (typically longer) tier,
+    /// recomputing its boosted weight from `new_lock`'s multiplier
+    Extend {
+        position_id: u64,
+        new_lock: Duration,
+    },
+
+    /// Deposits `reward_denom` funds sent with this message into the reward pool,
+    /// increasing `GLOBAL_REWARD_INDEX` pro-rata to the total staked weight
+    DistributeRewards {},
+    /// Settles and pays out the sender's accrued rewards
+    ClaimRewards {},
 
     /// Change the admin
     UpdateAdmin { admin: Option<String> },
@@ -45,22 +85,36 @@ pub enum ExecuteMsg {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ReceiveMsg {
-    /// Only valid cw20 message is to bond the tokens
-    Bond {},
+    /// Bonds the received cw20 tokens, for pools configured with a cw20 stake denom
+    Bond { lock_duration: Option<Duration> },
+    /// Deposits the received cw20 tokens into the reward pool, for pools configured
+    /// with a cw20 `reward_denom`. Mirrors `ExecuteMsg::DistributeRewards`, which is
+    /// used instead when `reward_denom` is native.
+    DistributeRewards {},
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
-    /// Claims shows the tokens in process of unbonding for this address
+    /// Claims shows the tokens in process of unbonding for this address. Claims that
+    /// share both a denom and a `release_at` are merged into one entry at unbond time,
+    /// so this stays short even for frequent unbonders.
     //  struct ClaimsResponse { pub claims: Vec<Claim>,}
-    Claims {
+    Claims { address: String },
+    /// Splits this address's claims as of `at_time` into what's claimable now versus
+    /// still locked, without the caller having to iterate the full claims Vec.
+    /// Returns ClaimableAtResponse.
+    ClaimableAt {
         address: String,
+        at_time: Expiration,
     },
-    // Show the number of tokens currently staked by this address.      
+    // Show the number of tokens currently staked by this address in the given denom
+    // (or, if `denom` is omitted, the first whitelisted denom, i.e. the only one in a
+    // one-denom pool).
     // struct StakedResponse { pub stake: Uint128, pub denom: Denom,}
     Staked {
         address: String,
+        denom: Option<Denom>,
     },
 
     /// Return AdminResponse            struct AdminResponse {pub admin: Option<String>,}  
@@ -79,6 +133,14 @@ pub enum QueryMsg {
     },
     /// Shows all registered hooks. Returns HooksResponse.  struct HooksResponse { pub hooks: Vec<String>,}
     Hooks {},
+    /// Returns this address's unclaimed rewards, settled plus accrued since its last
+    /// settlement, rounded down. Returns PendingRewardsResponse.
+    PendingRewards { address: String },
+    /// Returns this address's stake broken down by denom. Returns StakedByDenomResponse.
+    StakedByDenom { address: String },
+    /// Returns this address's locked positions, their boosted weight and unlock time.
+    /// Returns LockedPositionsResponse.
+    LockedPositions { address: String },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -86,3 +148,37 @@ pub struct StakedResponse {
     pub stake: Uint128,
     pub denom: Denom,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakedByDenomResponse {
+    pub stakes: Vec<StakedResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingRewardsResponse {
+    pub address: String,
+    pub pending_rewards: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LockPositionResponse {
+    pub position_id: u64,
+    pub denom: Denom,
+    pub amount: Uint128,
+    pub weight: u64,
+    pub locked_until: Expiration,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LockedPositionsResponse {
+    pub positions: Vec<LockPositionResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClaimableAtResponse {
+    /// sum of claims whose `release_at` has passed `at_time`
+    pub claimable: Uint128,
+    /// sum of claims still locked as of `at_time`; claims whose `release_at` can't be
+    /// compared with `at_time` (height vs time, or either one `Never`) are counted here
+    pub pending: Uint128,
+}
@@ -1,27 +1,66 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Decimal, Uint128};
 use cw20::Denom;
 use cw4::TOTAL_KEY;
-use cw_controllers::{Admin, Claims, Hooks};
+use cw_controllers::{Admin, Hooks};
 use cw_storage_plus::{Item, Map, SnapshotMap, Strategy};
-use cw_utils::Duration;
+use cw_utils::{Duration, Expiration};
 
-// A Claim allows a given address to claim an amount of tokens after a release date. 
-// When a claim is created: an address, amount and expiration are given.
-// Claims(Map<&Addr, Vec<Claim>>)      struct Claim {amount: Uint128,release_at: Expiration,}
-pub const CLAIMS: Claims = Claims::new("claims");
+/// A claim allows a given address to claim `amount` of `denom` back after `release_at`.
+/// Unlike `cw_controllers::Claim`, this tracks which denom it's for, since a multi-denom
+/// pool can have several unbondings in flight for the same address at once.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct Claim {
+    pub denom: Denom,
+    pub amount: Uint128,
+    pub release_at: Expiration,
+}
 
-// Duration is a delta of time.
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
-pub struct Config {
+pub struct ClaimsResponse {
+    pub claims: Vec<Claim>,
+}
+
+pub const CLAIMS: Map<&Addr, Vec<Claim>> = Map::new("claims");
+
+/// Per-denom staking parameters. A pool may whitelist several denoms, each with its
+/// own conversion rate and minimum bond, the way `StakeConfig` entries model one
+/// accepted LP token in a multi-staking contract.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct StakeConfig {
     /// denom of the token to stake
-    pub denom: Denom,      //enum Denom {Native(String), Cw20(Addr),} 
+    pub denom: Denom,      //enum Denom {Native(String), Cw20(Addr),}
                            // We can specify a String (coin denom) or an addr (contract address) for the Denomination
     pub tokens_per_weight: Uint128,     // Constant, will not change as we stake/bond new tokens.
     pub min_bond: Uint128,
+}
+
+/// One voluntary-lockup tier: staying locked for `duration` multiplies the bonded
+/// amount's base weight by `multiplier` (ve-token-style boosted voting power).
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct LockTier {
+    pub duration: Duration,
+    pub multiplier: Decimal,
+}
+
+/// Optional lock-tier schedule. Absent means bonding never locks tokens or boosts weight.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct LockConfig {
+    pub tiers: Vec<LockTier>,
+}
+
+// Duration is a delta of time.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct Config {
+    /// whitelisted stakeable denoms, each with its own rate and minimum bond
+    pub denoms: Vec<StakeConfig>,
     pub unbonding_period: Duration,
+    /// denom that `DistributeRewards` accepts and that `ClaimRewards` pays out
+    pub reward_denom: Denom,
+    /// lock tiers available to bonders for a weight boost; `None` disables locking
+    pub lock_config: Option<LockConfig>,
 }
 
 // ADMIN: Item< Option<Addr> >      struct Admin(Item<Option<Addr>>)   
@@ -44,4 +83,45 @@ pub const MEMBERS: SnapshotMap<&Addr, u64> = SnapshotMap::new(
     Strategy::EveryBlock,
 );
 
-pub const STAKE: Map<&Addr, Uint128> = Map::new("stake");
+// STAKE is keyed by (staker, denom) so a single address can hold positions in
+// several whitelisted denoms at once; MEMBERS weight is the sum across all of them.
+pub const STAKE: Map<(&Addr, String), Uint128> = Map::new("stake");
+
+/// Per-holder reward accrual checkpoint for the global-index reward distribution.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+pub struct HolderInfo {
+    /// value of GLOBAL_REWARD_INDEX the last time this holder was settled
+    pub reward_index: Decimal,
+    /// rewards settled but not yet claimed (fractional remainder kept here after a claim)
+    pub pending_rewards: Decimal,
+}
+
+/// Sum of `reward_amount / total_weight` over every `DistributeRewards` deposit.
+/// Settling a holder applies `(GLOBAL_REWARD_INDEX - holder.reward_index) * holder_weight`
+/// to their `pending_rewards`, then advances `holder.reward_index` to the current value.
+pub const GLOBAL_REWARD_INDEX: Item<Decimal> = Item::new("global_reward_index");
+
+/// Rewards received while TOTAL (the sum of membership weight) was zero, buffered
+/// until the next `DistributeRewards` call where `total_weight > 0`.
+pub const UNDISTRIBUTED_REWARDS: Item<Uint128> = Item::new("undistributed_rewards");
+
+pub const HOLDERS: Map<&Addr, HolderInfo> = Map::new("holders");
+
+/// A single locked bond position. The boosted weight (`base_weight * multiplier`,
+/// rounded down) is what gets added to the holder's `MEMBERS` entry; the tokens
+/// themselves stay in `STAKE` and can't be unbonded until `locked_until` passes.
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct LockPosition {
+    pub denom: Denom,
+    pub amount: Uint128,
+    pub base_weight: u64,
+    pub multiplier: Decimal,
+    pub locked_until: Expiration,
+}
+
+/// Lock positions, keyed by (owner, position_id)
+pub const LOCKS: Map<(&Addr, u64), LockPosition> = Map::new("locks");
+
+/// Last `position_id` handed out per address (absent before the first lock); the next
+/// one is this plus one, so `LOCKS` keys stay unique
+pub const LOCK_SEQNS: Map<&Addr, u64> = Map::new("lock_seqns");